@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::path::Path;
 
 #[derive(Clone)]
 pub enum Node<V> {
     Directory {
         name: String,
-        children: Vec<Box<Node<V>>>,
+        children: HashMap<String, Box<Node<V>>>,
     },
     File {
         name: String,
@@ -22,11 +27,9 @@ where
         }
     }
 
-    pub fn get_child(&self, name: &str) -> Option<&Box<Node<V>>> {
+    pub fn get_child(&self, name: &str) -> Option<&Node<V>> {
         match self {
-            Node::Directory { children, .. } => {
-                children.iter().find(|child| child.get_name() == name)
-            }
+            Node::Directory { children, .. } => children.get(name).map(|child| child.as_ref()),
             Node::File { .. } => None,
         }
     }
@@ -35,7 +38,7 @@ where
         match self {
             Node::Directory {
                 ref mut children, ..
-            } => children.iter_mut().find(|child| child.get_name() == name),
+            } => children.get_mut(name),
             Node::File { .. } => None,
         }
     }
@@ -45,15 +48,18 @@ where
             Node::Directory { children, .. } => {
                 let new_node = Node::Directory {
                     name: name.to_string(),
-                    children: vec![],
+                    children: HashMap::new(),
                 };
-                children.push(Box::new(new_node));
-                children.last_mut().unwrap()
+                children.insert(name.to_string(), Box::new(new_node));
+                children.get_mut(name).unwrap()
             }
             Node::File { .. } => panic!("Cannot make directory on file"),
         }
     }
 
+    /// Folds over every file in the subtree, in unspecified order (children
+    /// are stored in a `HashMap`, not a `Vec`, so no traversal order is
+    /// guaranteed).
     pub fn reduce<T, F>(&self, accumulator: T, f: F) -> T
     where
         F: Fn(T,&String, V) -> T + Copy,
@@ -61,7 +67,7 @@ where
         match self {
             Node::File { size, name } => f(accumulator, name, size.clone()),
             Node::Directory { children, .. } => children
-                .iter()
+                .values()
                 .fold(accumulator, |acc, child| child.reduce(acc,  f)),
         }
     }
@@ -72,12 +78,48 @@ where
         match self {
             Node::File { size, .. } => f(accumulator, size.clone()),
             Node::Directory { children, .. } => children
-                .iter()
+                .values()
                 .fold(accumulator, |acc, child| child.value_reduce(acc, f)),
         }
     }
 }
 
+/// Breadth-first iterator over every node in a tree, yielding each node's
+/// full slash-joined path alongside the node itself.
+///
+/// Traversal is lazy: children are only discovered and queued once their
+/// parent directory is popped, so no intermediate `Vec` of the whole tree
+/// is ever built.
+pub struct NodeIter<'a, V> {
+    queue: VecDeque<(String, &'a Node<V>)>,
+}
+
+impl<'a, V> NodeIter<'a, V> {
+    fn new(root: &'a Node<V>) -> Self {
+        let mut queue = VecDeque::new();
+        queue.push_back((String::from("root"), root));
+        NodeIter { queue }
+    }
+}
+
+impl<'a, V> Iterator for NodeIter<'a, V>
+where
+    V: Clone,
+{
+    type Item = (String, &'a Node<V>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.queue.pop_front()?;
+        if let Node::Directory { children, .. } = node {
+            for child in children.values() {
+                self.queue
+                    .push_back((format!("{path}/{}", child.get_name()), child));
+            }
+        }
+        Some((path, node))
+    }
+}
+
 pub struct FSTreeMap<V> {
     pub(crate) root: Box<Node<V>>,
 }
@@ -90,11 +132,25 @@ where
         FSTreeMap {
             root: Box::new(Node::Directory {
                 name: String::from("root"),
-                children: vec![],
+                children: HashMap::new(),
             }),
         }
     }
+}
 
+impl<V> Default for FSTreeMap<V>
+where
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> FSTreeMap<V>
+where
+    V: Clone,
+{
     pub fn get_size(&self, path: &str) -> &V {
         match self.get_node(path).unwrap() {
             Node::File { size, .. } => size,
@@ -106,18 +162,12 @@ where
         let mut current = &self.root;
         for part in path.split('/') {
             match **current {
-                Node::Directory { ref children, .. } => {
-                    match children.iter().find(|child| {
-                        let node = child.as_ref();
-                        let name = node.get_name();
-                        return *name == String::from(part);
-                    }) {
-                        Some(child) => {
-                            current = child;
-                        }
-                        None => return None,
+                Node::Directory { ref children, .. } => match children.get(part) {
+                    Some(child) => {
+                        current = child;
                     }
-                }
+                    None => return None,
+                },
                 _ => panic!("Path is not a directory"),
             }
         }
@@ -126,7 +176,7 @@ where
 
     pub fn insert(&mut self, path: &str, value: V) {
         let mut current = &mut self.root;
-        let full_path_split: Vec<&str> = path.split('/').into_iter().collect();
+        let full_path_split: Vec<&str> = path.split('/').collect();
         let dirpath = &full_path_split[..full_path_split.len() - 1];
         let stem = full_path_split.last().unwrap();
         for part in dirpath {
@@ -142,7 +192,7 @@ where
                     size: value,
                 };
 
-                children.push(Box::new(new_node));
+                children.insert(stem.to_string(), Box::new(new_node));
             }
 
             Node::File { .. } => panic!("Path already exists"),
@@ -151,7 +201,7 @@ where
 
     pub fn remove(&mut self, path: &str) {
         let mut current = &mut self.root;
-        let full_path_split: Vec<&str> = path.split('/').into_iter().collect();
+        let full_path_split: Vec<&str> = path.split('/').collect();
         let dirpath = &full_path_split[..full_path_split.len() - 1];
         let stem = full_path_split.last().unwrap();
         for part in dirpath {
@@ -162,27 +212,146 @@ where
             Node::Directory {
                 ref mut children, ..
             } => {
-                children.retain(|child| {
-                    let node = child.as_ref();
-                    let name = node.get_name();
-                    return *name != String::from(*stem);
-                });
+                children.remove(*stem);
             }
 
             Node::File { .. } => panic!("Path is a file"),
         }
     }
 
-    pub fn get_children(&self, path: &str) -> Option<&Vec<Box<Node<V>>>> {
+    /// Returns the children of the directory at `path`. Iteration order is
+    /// unspecified since children are stored in a `HashMap` keyed by
+    /// basename.
+    pub fn get_children(&self, path: &str) -> Option<impl Iterator<Item = &Box<Node<V>>>> {
         match self.get_node(path) {
-            Some(Node::Directory { children, .. }) => Some(children),
+            Some(Node::Directory { children, .. }) => Some(children.values()),
             _ => None,
         }
     }
 
+    fn get_mut_node(&mut self, path: &str) -> Option<&mut Box<Node<V>>> {
+        let mut current = &mut self.root;
+        for part in path.split('/') {
+            current = current.get_mut_child(part)?;
+        }
+        Some(current)
+    }
+
+    /// Renames the node at `path` in place, keeping it under the same
+    /// parent directory.
+    pub fn rename(&mut self, path: &str, new_name: &str) {
+        let full_path_split: Vec<&str> = path.split('/').collect();
+        let dirpath = &full_path_split[..full_path_split.len() - 1];
+        let stem = full_path_split.last().unwrap();
+        let mut current = &mut self.root;
+        for part in dirpath {
+            current = current.get_mut_child(part).unwrap();
+        }
+
+        match **current {
+            Node::Directory {
+                ref mut children, ..
+            } => {
+                let mut node = children.remove(*stem).expect("path does not exist");
+                match *node {
+                    Node::Directory { ref mut name, .. } => *name = new_name.to_string(),
+                    Node::File { ref mut name, .. } => *name = new_name.to_string(),
+                }
+                children.insert(new_name.to_string(), node);
+            }
+            Node::File { .. } => panic!("Path is a file"),
+        }
+    }
+
+    /// Detaches the subtree at `from` and re-attaches it under `to_parent`,
+    /// creating `to_parent` (and any missing ancestors) if needed.
+    ///
+    /// Errors instead of corrupting the tree if `to_parent` is `from` itself
+    /// or one of its descendants.
+    pub fn move_node(&mut self, from: &str, to_parent: &str) -> Result<(), String> {
+        if to_parent == from || to_parent.starts_with(&format!("{from}/")) {
+            return Err(format!(
+                "cannot move `{from}` into its own descendant `{to_parent}`"
+            ));
+        }
+
+        let full_path_split: Vec<&str> = from.split('/').collect();
+        let dirpath = &full_path_split[..full_path_split.len() - 1];
+        let stem = *full_path_split.last().unwrap();
+
+        let mut current = &mut self.root;
+        for part in dirpath {
+            current = current
+                .get_mut_child(part)
+                .ok_or_else(|| format!("no such path: {from}"))?;
+        }
+        let node = match **current {
+            Node::Directory {
+                ref mut children, ..
+            } => children
+                .remove(stem)
+                .ok_or_else(|| format!("no such path: {from}"))?,
+            Node::File { .. } => return Err(format!("{from} does not resolve to a node")),
+        };
+
+        self.make_directory(to_parent);
+        let name = node.get_name().clone();
+        match self.get_mut_node(to_parent) {
+            Some(dest) => match **dest {
+                Node::Directory {
+                    ref mut children, ..
+                } => {
+                    children.insert(name, node);
+                    Ok(())
+                }
+                Node::File { .. } => Err(format!("{to_parent} is not a directory")),
+            },
+            None => Err(format!("no such path: {to_parent}")),
+        }
+    }
+
+    /// Deep-clones the subtree at `from` and inserts the copy at `to`,
+    /// creating `to`'s parent directories if needed.
+    pub fn copy(&mut self, from: &str, to: &str) -> Result<(), String> {
+        let node = self
+            .get_node(from)
+            .ok_or_else(|| format!("no such path: {from}"))?
+            .clone();
+
+        let full_path_split: Vec<&str> = to.split('/').collect();
+        let dirpath = &full_path_split[..full_path_split.len() - 1];
+        let stem = *full_path_split.last().unwrap();
+
+        if !dirpath.is_empty() {
+            self.make_directory(&dirpath.join("/"));
+        }
+
+        let mut current = &mut self.root;
+        for part in dirpath {
+            current = current
+                .get_mut_child(part)
+                .ok_or_else(|| format!("no such path: {to}"))?;
+        }
+
+        match **current {
+            Node::Directory {
+                ref mut children, ..
+            } => {
+                let mut copied = node;
+                match &mut copied {
+                    Node::Directory { name, .. } => *name = stem.to_string(),
+                    Node::File { name, .. } => *name = stem.to_string(),
+                }
+                children.insert(stem.to_string(), Box::new(copied));
+                Ok(())
+            }
+            Node::File { .. } => Err(format!("{to} parent is a file")),
+        }
+    }
+
     pub fn make_directory(&mut self, path: &str) {
         let mut current = &mut self.root;
-        let full_path_split: Vec<&str> = path.split('/').into_iter().collect();
+        let full_path_split: Vec<&str> = path.split('/').collect();
         let dirpath = &full_path_split;
         for part in dirpath {
             let maybe_directory = current.get_child(part);
@@ -199,7 +368,7 @@ where
 
     pub fn insert_with_parents(&mut self, path: &str, value: V) {
         let mut current = &mut self.root;
-        let full_path_split: Vec<&str> = path.split('/').into_iter().collect();
+        let full_path_split: Vec<&str> = path.split('/').collect();
         let dirpath = &full_path_split[..full_path_split.len() - 1];
         let stem = full_path_split.last().unwrap();
         for part in dirpath {
@@ -223,7 +392,7 @@ where
                     size: value,
                 };
 
-                children.push(Box::new(new_node));
+                children.insert(stem.to_string(), Box::new(new_node));
             }
 
             Node::File { .. } => panic!("Path already exists"),
@@ -243,6 +412,33 @@ where
         self.root.reduce(accumulator, f)
     }
 
+    /// Folds over every file in the subtree rooted at `path`, in unspecified
+    /// order. Returns `accumulator` unchanged if `path` does not exist.
+    pub fn directory_reduce<T, F>(&self, path: &str, accumulator: T, f: F) -> T
+    where
+        F: Fn(T, &String, V) -> T + Copy,
+    {
+        match self.get_node(path) {
+            Some(node) => node.reduce(accumulator, f),
+            None => accumulator,
+        }
+    }
+
+    /// Breadth-first iterator over every node (files and directories) in
+    /// the tree, yielding each node's full slash-joined path.
+    pub fn nodes(&self) -> NodeIter<'_, V> {
+        NodeIter::new(&self.root)
+    }
+
+    /// Breadth-first iterator over every file in the tree, yielding each
+    /// file's full slash-joined path alongside its value.
+    pub fn iter(&self) -> impl Iterator<Item = (String, &V)> {
+        self.nodes().filter_map(|(path, node)| match node {
+            Node::File { size, .. } => Some((path, size)),
+            Node::Directory { .. } => None,
+        })
+    }
+
     pub fn any<F>(&self, f: F) -> bool
     where
         F: Fn(&String, &V) -> bool,
@@ -258,6 +454,27 @@ where
     pub fn value_sum(&self) -> V {
         self.value_reduce(V::default(), |acc, x| acc + x)
     }
+
+    /// Recursive sum of every file in the subtree rooted at `path`. Returns
+    /// `V::default()` if `path` does not exist.
+    pub fn subtree_sum(&self, path: &str) -> V {
+        self.directory_reduce(path, V::default(), |acc, _, x| acc + x)
+    }
+
+    /// Folds over every directory in the tree, handing the callback the
+    /// directory's full path and the recursive sum of its descendant files.
+    pub fn reduce_dirs<T, F>(&self, accumulator: T, f: F) -> T
+    where
+        F: Fn(T, &str, V) -> T + Copy,
+    {
+        self.nodes().fold(accumulator, |acc, (path, node)| match node {
+            Node::Directory { .. } => {
+                let subtree_value = node.value_reduce(V::default(), |acc, x| acc + x);
+                f(acc, &path, subtree_value)
+            }
+            Node::File { .. } => acc,
+        })
+    }
 }
 
 impl<V> FSTreeMap<V>
@@ -272,7 +489,7 @@ where
         match node {
             Node::Directory { name, children } => {
                 println!("{}{}", " ".repeat(depth), name);
-                for child in children {
+                for child in children.values() {
                     self.print_tree_recursive(child, depth + 1);
                 }
             }
@@ -283,6 +500,134 @@ where
     }
 }
 
+impl FSTreeMap<u64> {
+    /// Walks a real directory on disk and builds a tree mirroring it, using
+    /// each file's byte size (from its directory entry's metadata, which
+    /// does not follow symlinks) as its value.
+    pub fn from_path(root: &Path) -> io::Result<FSTreeMap<u64>> {
+        let mut tree = FSTreeMap::new();
+        Self::fill_from_path(&mut tree, root, "")?;
+        Ok(tree)
+    }
+
+    fn fill_from_path(tree: &mut FSTreeMap<u64>, dir: &Path, prefix: &str) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let rel_path = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            };
+
+            if path.is_dir() {
+                tree.make_directory(&rel_path);
+                Self::fill_from_path(tree, &path, &rel_path)?;
+            } else {
+                let size = entry.metadata()?.len();
+                tree.insert_with_parents(&rel_path, size);
+            }
+        }
+        Ok(())
+    }
+
+    /// Materializes the tree onto disk under `root`, creating directories
+    /// and zero-filled placeholder files whose length matches each `File`
+    /// node's value.
+    pub fn write_to(&self, root: &Path) -> io::Result<()> {
+        fs::create_dir_all(root)?;
+        if let Node::Directory { children, .. } = self.root.as_ref() {
+            for child in children.values() {
+                Self::write_node(child, &root.join(child.get_name()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_node(node: &Node<u64>, path: &Path) -> io::Result<()> {
+        match node {
+            Node::Directory { children, .. } => {
+                fs::create_dir_all(path)?;
+                for child in children.values() {
+                    Self::write_node(child, &path.join(child.get_name()))?;
+                }
+                Ok(())
+            }
+            Node::File { size, .. } => {
+                let file = fs::File::create(path)?;
+                file.set_len(*size)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V> FSTreeMap<V>
+where
+    V: Clone + serde::Serialize,
+{
+    /// Serializes the tree to a flat, length-prefixed record stream of
+    /// `(full_path, value)` pairs, one per file — inspired by the
+    /// Mercurial dirstate-v2 on-disk format rather than a deeply nested
+    /// recursive encoding. Directory structure is implied by each path and
+    /// rebuilt on load via `insert_with_parents`.
+    pub fn to_bytes(&self) -> serde_json::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        for (path, value) in self.iter() {
+            let value_bytes = serde_json::to_vec(value)?;
+            out.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            out.extend_from_slice(path.as_bytes());
+            out.extend_from_slice(&(value_bytes.len() as u32).to_le_bytes());
+            out.extend_from_slice(&value_bytes);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V> FSTreeMap<V>
+where
+    V: Clone + serde::de::DeserializeOwned,
+{
+    /// Rebuilds a tree from the record stream produced by `to_bytes`,
+    /// replaying each `(full_path, value)` record through
+    /// `insert_with_parents` so the directory structure is reconstructed
+    /// deterministically rather than decoded as nested records.
+    pub fn from_bytes(bytes: &[u8]) -> serde_json::Result<FSTreeMap<V>> {
+        use serde::de::Error;
+
+        fn take<'a>(bytes: &'a [u8], cursor: &mut usize, len: usize) -> serde_json::Result<&'a [u8]> {
+            let slice = bytes
+                .get(*cursor..*cursor + len)
+                .ok_or_else(|| serde_json::Error::custom("truncated record stream"))?;
+            *cursor += len;
+            Ok(slice)
+        }
+
+        fn take_len(bytes: &[u8], cursor: &mut usize) -> serde_json::Result<usize> {
+            let slice = take(bytes, cursor, 4)?;
+            Ok(u32::from_le_bytes(slice.try_into().unwrap()) as usize)
+        }
+
+        let mut tree = FSTreeMap::new();
+        let mut cursor = 0;
+        while cursor < bytes.len() {
+            let path_len = take_len(bytes, &mut cursor)?;
+            let path = String::from_utf8(take(bytes, &mut cursor, path_len)?.to_vec())
+                .map_err(|e| serde_json::Error::custom(e.to_string()))?;
+
+            let value_len = take_len(bytes, &mut cursor)?;
+            let value: V = serde_json::from_slice(take(bytes, &mut cursor, value_len)?)?;
+
+            let relative_path = path.strip_prefix("root/").unwrap_or(&path);
+            tree.insert_with_parents(relative_path, value);
+        }
+        Ok(tree)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,7 +646,7 @@ mod tests {
     fn can_create_directory_node() {
         let dir = Node::Directory::<usize> {
             name: "home".to_string(),
-            children: vec![],
+            children: HashMap::new(),
         };
         assert_eq!(dir.get_name(), "home");
     }
@@ -337,8 +682,171 @@ mod tests {
         let mut tree = FSTreeMap::new();
         tree.insert_with_parents("home/users/arthur/answer.txt", 42);
         tree.insert_with_parents("home/users/arthur/password.txt", 128);
-        assert_eq!(tree.any(|name, _| name.contains("passw")), true);
-        assert_eq!(tree.any(|name, _| name.contains("Ideas")), false);
-        assert_eq!(tree.any(|_, size| size == &42), true);
+        assert!(tree.any(|name, _| name.contains("passw")));
+        assert!(!tree.any(|name, _| name.contains("Ideas")));
+        assert!(tree.any(|_, size| size == &42));
+    }
+
+    #[test]
+    fn test_iter_yields_full_paths() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+        tree.insert_with_parents("home/users/arthur/password.txt", 128);
+
+        let mut files: Vec<(String, usize)> = tree.iter().map(|(p, v)| (p, *v)).collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                (
+                    "root/home/users/arthur/answer.txt".to_string(),
+                    42
+                ),
+                (
+                    "root/home/users/arthur/password.txt".to_string(),
+                    128
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nodes_visits_directories_too() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+
+        let paths: Vec<String> = tree.nodes().map(|(p, _)| p).collect();
+        assert!(paths.contains(&"root".to_string()));
+        assert!(paths.contains(&"root/home".to_string()));
+        assert!(paths.contains(&"root/home/users/arthur/answer.txt".to_string()));
+    }
+
+    #[test]
+    fn test_subtree_sum() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+        tree.insert_with_parents("home/users/arthur/password.txt", 128);
+        tree.insert_with_parents("home/users/trillian/towel.txt", 7);
+
+        assert_eq!(tree.subtree_sum("home/users/arthur"), 170);
+        assert_eq!(tree.subtree_sum("home/users/trillian"), 7);
+        assert_eq!(tree.subtree_sum("home"), 177);
+    }
+
+    #[test]
+    fn test_subtree_sum_missing_path() {
+        let tree: FSTreeMap<usize> = FSTreeMap::new();
+        assert_eq!(tree.subtree_sum("nowhere"), 0);
+    }
+
+    #[test]
+    fn test_reduce_dirs() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+        tree.insert_with_parents("home/users/arthur/password.txt", 128);
+        tree.insert_with_parents("home/users/trillian/towel.txt", 7);
+
+        let mut sizes: Vec<(String, usize)> = tree.reduce_dirs(vec![], |mut acc, path, size| {
+            acc.push((path.to_string(), size));
+            acc
+        });
+        sizes.sort();
+
+        assert_eq!(
+            sizes,
+            vec![
+                ("root".to_string(), 177),
+                ("root/home".to_string(), 177),
+                ("root/home/users".to_string(), 177),
+                ("root/home/users/arthur".to_string(), 170),
+                ("root/home/users/trillian".to_string(), 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_path_and_write_to_round_trip() {
+        let scratch = std::env::temp_dir().join(format!("fstree_test_{}", std::process::id()));
+        let source = scratch.join("source");
+        std::fs::create_dir_all(source.join("home/users/arthur")).unwrap();
+        std::fs::write(source.join("home/users/arthur/answer.txt"), [0u8; 42]).unwrap();
+        std::fs::write(source.join("home/users/arthur/password.txt"), [0u8; 128]).unwrap();
+
+        let tree = FSTreeMap::from_path(&source).unwrap();
+        assert_eq!(tree.subtree_sum("home/users/arthur"), 170);
+
+        let dest = scratch.join("dest");
+        tree.write_to(&dest).unwrap();
+        let metadata =
+            std::fs::metadata(dest.join("home/users/arthur/answer.txt")).unwrap();
+        assert_eq!(metadata.len(), 42);
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+
+        tree.rename("home/users/arthur/answer.txt", "life.txt");
+
+        assert!(tree.get_node("home/users/arthur/answer.txt").is_none());
+        assert_eq!(*tree.get_size("home/users/arthur/life.txt"), 42);
+    }
+
+    #[test]
+    fn test_move_node() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+        tree.make_directory("home/users/trillian");
+
+        tree.move_node("home/users/arthur/answer.txt", "home/users/trillian")
+            .unwrap();
+
+        assert!(tree.get_node("home/users/arthur/answer.txt").is_none());
+        assert_eq!(*tree.get_size("home/users/trillian/answer.txt"), 42);
+    }
+
+    #[test]
+    fn test_move_node_into_own_descendant_errors() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+
+        assert!(tree.move_node("home/users", "home/users/arthur").is_err());
+        // the tree must be untouched after the rejected move
+        assert_eq!(*tree.get_size("home/users/arthur/answer.txt"), 42);
+    }
+
+    #[test]
+    fn test_copy() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+
+        tree.copy(
+            "home/users/arthur/answer.txt",
+            "home/users/trillian/answer.txt",
+        )
+        .unwrap();
+
+        assert_eq!(*tree.get_size("home/users/arthur/answer.txt"), 42);
+        assert_eq!(*tree.get_size("home/users/trillian/answer.txt"), 42);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_bytes_from_bytes_round_trip() {
+        let mut tree = FSTreeMap::new();
+        tree.insert_with_parents("home/users/arthur/answer.txt", 42);
+        tree.insert_with_parents("home/users/arthur/password.txt", 128);
+
+        let bytes = tree.to_bytes().unwrap();
+        let restored: FSTreeMap<usize> = FSTreeMap::from_bytes(&bytes).unwrap();
+
+        assert_eq!(restored.value_sum(), tree.value_sum());
+        assert_eq!(
+            *restored.get_size("home/users/arthur/answer.txt"),
+            42
+        );
     }
 }